@@ -1,12 +1,15 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryInto;
 use std::ffi::CStr;
-use std::io::Read;
+use std::io::{BufRead, Read, Write};
 use std::str::FromStr;
 
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
 use fallible_iterator::FallibleIterator;
-use serde::Serialize;
+use openssl::hash::{Hasher, MessageDigest};
+use openssl::pkey::{PKey, Public};
+use openssl::sign::Verifier as PkeyVerifier;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tpmless_tpm2::{DigestAlgorithm, PcrExtender, PcrExtenderBuilder};
 
@@ -30,25 +33,111 @@ pub enum Error {
     UnknownDigestAlgo(String),
     #[error("Error in TPMLess")]
     Tpmless(#[from] tpmless_tpm2::Error),
+    #[error("Unsupported signature version {0}")]
+    UnsupportedSignatureVersion(u8),
+    #[error("No key with keyid {0:08x} in keyring")]
+    UnknownKeyId(u32),
+    #[error("Digest recomputed from contents does not match the measured digest")]
+    DigestMismatch,
+    #[error("Signature verification failed")]
+    BadSignature,
+    #[error("Event data carries no signature to verify")]
+    NotSigned,
+    #[error("OpenSSL error: {0}")]
+    OpenSsl(#[from] openssl::error::ErrorStack),
+    #[error("Malformed ASCII measurement line: {0}")]
+    AsciiFormat(String),
+    #[error("Hex decode error: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("Integer parse error: {0}")]
+    ParseInt(#[from] std::num::ParseIntError),
+    #[error("Replayed PCR values do not match the expected values ({} mismatch(es))", .0.len())]
+    PcrMismatch(Vec<PcrMismatch>),
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Digest {
     algo: DigestAlgorithm,
     #[serde(with = "hex")]
     digest: Vec<u8>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct Signature {}
+/// Map a `DigestAlgorithm` to the matching OpenSSL message digest.
+fn message_digest(algo: DigestAlgorithm) -> Result<MessageDigest, Error> {
+    Ok(match algo {
+        DigestAlgorithm::Sha1 => MessageDigest::sha1(),
+        DigestAlgorithm::Sha256 => MessageDigest::sha256(),
+        DigestAlgorithm::Sha384 => MessageDigest::sha384(),
+        DigestAlgorithm::Sha512 => MessageDigest::sha512(),
+        other => return Err(Error::UnknownDigestAlgo(format!("{:?}", other))),
+    })
+}
 
-#[derive(Debug, Serialize)]
-pub struct Buffer {}
+/// Map an index into the kernel `hash_algo` enum (see `include/uapi/linux/hash_info.h`)
+/// to a `DigestAlgorithm`.
+fn digest_algo_from_hash_info(idx: u8) -> Result<DigestAlgorithm, Error> {
+    Ok(match idx {
+        2 => DigestAlgorithm::Sha1,
+        4 => DigestAlgorithm::Sha256,
+        5 => DigestAlgorithm::Sha384,
+        6 => DigestAlgorithm::Sha512,
+        other => return Err(Error::UnknownDigestAlgo(format!("hash_algo index {}", other))),
+    })
+}
 
-#[derive(Debug, Serialize)]
-pub struct Modsig {}
+/// Inverse of [`digest_algo_from_hash_info`].
+fn hash_info_from_digest_algo(algo: DigestAlgorithm) -> Result<u8, Error> {
+    Ok(match algo {
+        DigestAlgorithm::Sha1 => 2,
+        DigestAlgorithm::Sha256 => 4,
+        DigestAlgorithm::Sha384 => 5,
+        DigestAlgorithm::Sha512 => 6,
+        other => return Err(Error::UnknownDigestAlgo(format!("{:?}", other))),
+    })
+}
 
-#[derive(Debug, Serialize)]
+/// The kernel's textual name for a digest algorithm, as used in the `d-ng`
+/// field and the ASCII measurement format.
+fn algo_name(algo: DigestAlgorithm) -> Result<&'static str, Error> {
+    Ok(match algo {
+        DigestAlgorithm::Sha1 => "sha1",
+        DigestAlgorithm::Sha256 => "sha256",
+        DigestAlgorithm::Sha384 => "sha384",
+        DigestAlgorithm::Sha512 => "sha512",
+        other => return Err(Error::UnknownDigestAlgo(format!("{:?}", other))),
+    })
+}
+
+/// A parsed IMA `signature_v2_hdr` as emitted in the `sig` template field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Signature {
+    /// Digest algorithm the signature was computed over.
+    pub algo: DigestAlgorithm,
+    /// IMA keyid: the last four bytes of the SHA-1 over the signing key's
+    /// `subjectPublicKey` bit string, as computed by `ima_keyid`.
+    pub keyid: u32,
+    /// The raw signature bytes.
+    #[serde(with = "hex")]
+    pub signature: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Buffer {
+    /// The raw `buf` field payload.
+    #[serde(with = "hex")]
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Modsig {
+    /// `d-modsig`: the file digest the appended signature was computed over.
+    digest: Digest,
+    /// `modsig`: the raw appended (PKCS#7) module-style signature.
+    #[serde(with = "hex")]
+    signature: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum EventData {
     // "d|n"
@@ -65,7 +154,9 @@ pub enum EventData {
     ImaSig {
         digest: Digest,
         name: String,
-        signature: Signature,
+        // An unsigned file under an `ima-sig` policy is logged with a
+        // zero-length sig field, represented here as `None`.
+        signature: Option<Signature>,
     },
     // "d-ng|n-ng|buf"
     ImaBuf {
@@ -77,21 +168,140 @@ pub enum EventData {
     ImaModsig {
         digest: Digest,
         name: String,
-        signature: Signature,
+        signature: Option<Signature>,
         modsig: Modsig,
     },
 }
 
-fn parse_signature<R: Read>(reader: &mut R) -> Result<Signature, Error> {
-    todo!();
+/// `type` field of `signature_v2_hdr` for an EVM/IMA xattr digital signature.
+const EVM_IMA_XATTR_DIGSIG: u8 = 3;
+
+fn parse_signature<R: Read>(reader: &mut R) -> Result<Option<Signature>, Error> {
+    // The sig field is length-prefixed; an empty field means the entry was not signed.
+    let len = reader.read_u32::<LittleEndian>()?;
+    if len == 0 {
+        return Ok(None);
+    }
+
+    // signature_v2_hdr
+    let sig_type = reader.read_u8()?;
+    if sig_type != EVM_IMA_XATTR_DIGSIG {
+        return Err(Error::DataError);
+    }
+    let version = reader.read_u8()?;
+    if version != 2 {
+        return Err(Error::UnsupportedSignatureVersion(version));
+    }
+    let algo = digest_algo_from_hash_info(reader.read_u8()?)?;
+    let keyid = reader.read_u32::<BigEndian>()?;
+    let sig_size = reader.read_u16::<BigEndian>()?;
+    let mut signature = zeroed_vec(sig_size as usize);
+    reader.read_exact(&mut signature)?;
+
+    Ok(Some(Signature {
+        algo,
+        keyid,
+        signature,
+    }))
 }
 
 fn parse_buffer<R: Read>(reader: &mut R) -> Result<Buffer, Error> {
-    todo!();
+    let len = reader.read_u32::<LittleEndian>()?;
+    let mut data = zeroed_vec(len as usize);
+    reader.read_exact(&mut data)?;
+    Ok(Buffer { data })
 }
 
 fn parse_modsig<R: Read>(reader: &mut R) -> Result<Modsig, Error> {
-    todo!();
+    // "d-modsig|modsig": a digest of the unsigned file followed by the raw
+    // appended signature.
+    let digest = parse_digest(false, reader)?;
+    let len = reader.read_u32::<LittleEndian>()?;
+    let mut signature = zeroed_vec(len as usize);
+    reader.read_exact(&mut signature)?;
+    Ok(Modsig { digest, signature })
+}
+
+// --- Field serializers: exact inverses of the `parse_*` helpers above. ---
+
+fn write_digest<W: Write>(
+    is_legacy_ima_template: bool,
+    digest: &Digest,
+    writer: &mut W,
+) -> Result<(), Error> {
+    if is_legacy_ima_template {
+        writer.write_all(&digest.digest)?;
+    } else {
+        let mut field = Vec::with_capacity(digest.digest.len() + 8);
+        field.extend_from_slice(algo_name(digest.algo)?.as_bytes());
+        field.push(b':');
+        field.push(0);
+        field.extend_from_slice(&digest.digest);
+        writer.write_u32::<LittleEndian>(field.len() as u32)?;
+        writer.write_all(&field)?;
+    }
+    Ok(())
+}
+
+fn write_name<W: Write>(nul_terminated: bool, name: &str, writer: &mut W) -> Result<(), Error> {
+    // The legacy `ima` `n` field is plain text, while every later template's
+    // name field is NUL-terminated with the terminator counted in the length
+    // prefix. Re-emit exactly what `parse_name` would have stripped.
+    let mut field = name.as_bytes().to_vec();
+    if nul_terminated {
+        field.push(0);
+    }
+    writer.write_u32::<LittleEndian>(field.len() as u32)?;
+    writer.write_all(&field)?;
+    Ok(())
+}
+
+/// Serialize a `signature_v2_hdr` back to its raw field bytes, without the
+/// leading u32 length prefix.
+fn signature_field_bytes(signature: &Signature) -> Result<Vec<u8>, Error> {
+    let mut field = Vec::with_capacity(signature.signature.len() + 9);
+    field.push(EVM_IMA_XATTR_DIGSIG);
+    field.push(2); // version
+    field.push(hash_info_from_digest_algo(signature.algo)?);
+    field.extend_from_slice(&signature.keyid.to_be_bytes());
+    field.extend_from_slice(&(signature.signature.len() as u16).to_be_bytes());
+    field.extend_from_slice(&signature.signature);
+    Ok(field)
+}
+
+/// Hex-encode a signature field for the ASCII format; an unsigned entry is
+/// rendered as an empty field.
+fn optional_signature_hex(signature: &Option<Signature>) -> Result<String, Error> {
+    Ok(match signature {
+        None => String::new(),
+        Some(signature) => hex::encode(signature_field_bytes(signature)?),
+    })
+}
+
+fn write_signature<W: Write>(signature: &Option<Signature>, writer: &mut W) -> Result<(), Error> {
+    match signature {
+        // An unsigned entry is a zero-length sig field.
+        None => writer.write_u32::<LittleEndian>(0)?,
+        Some(signature) => {
+            let field = signature_field_bytes(signature)?;
+            writer.write_u32::<LittleEndian>(field.len() as u32)?;
+            writer.write_all(&field)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_buffer<W: Write>(buffer: &Buffer, writer: &mut W) -> Result<(), Error> {
+    writer.write_u32::<LittleEndian>(buffer.data.len() as u32)?;
+    writer.write_all(&buffer.data)?;
+    Ok(())
+}
+
+fn write_modsig<W: Write>(modsig: &Modsig, writer: &mut W) -> Result<(), Error> {
+    write_digest(false, &modsig.digest, writer)?;
+    writer.write_u32::<LittleEndian>(modsig.signature.len() as u32)?;
+    writer.write_all(&modsig.signature)?;
+    Ok(())
 }
 
 fn parse_digest<R: Read>(is_legacy_ima_template: bool, reader: &mut R) -> Result<Digest, Error> {
@@ -192,7 +402,340 @@ impl EventData {
     }
 }
 
-#[derive(Debug, Serialize)]
+impl EventData {
+    /// Verify a signed entry against a `Verifier` keyring, given the current
+    /// contents of the measured file.
+    ///
+    /// This recomputes the file digest, checks it against the digest recorded
+    /// in the log, and then validates the IMA signature over those contents
+    /// using the key whose keyid matches the one stored in the signature.
+    pub fn verify(&self, verifier: &Verifier, contents: &[u8]) -> Result<(), Error> {
+        let (digest, signature) = match self {
+            EventData::ImaSig {
+                digest, signature, ..
+            }
+            | EventData::ImaModsig {
+                digest, signature, ..
+            } => (digest, signature),
+            _ => return Err(Error::NotSigned),
+        };
+        let signature = signature.as_ref().ok_or(Error::NotSigned)?;
+
+        let mut hasher = Hasher::new(message_digest(digest.algo)?)?;
+        hasher.update(contents)?;
+        if hasher.finish()?.as_ref() != digest.digest.as_slice() {
+            return Err(Error::DigestMismatch);
+        }
+
+        verifier.verify(signature, contents)
+    }
+}
+
+impl EventData {
+    /// The template name this event data belongs to.
+    fn template_name(&self) -> &'static str {
+        match self {
+            EventData::Ima { .. } => "ima",
+            EventData::ImaNg { .. } => "ima-ng",
+            EventData::ImaSig { .. } => "ima-sig",
+            EventData::ImaBuf { .. } => "ima-buf",
+            EventData::ImaModsig { .. } => "ima-modsig",
+        }
+    }
+
+    /// Serialize the event data into the binary template-data layout, i.e. the
+    /// concatenation of length-prefixed fields that follows the per-event
+    /// header. This is the exact inverse of [`EventData::parse`].
+    fn write_binary<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        match self {
+            EventData::Ima { digest, name } => {
+                write_digest(true, digest, writer)?;
+                write_name(false, name, writer)?;
+            }
+            EventData::ImaNg { digest, name } => {
+                write_digest(false, digest, writer)?;
+                write_name(true, name, writer)?;
+            }
+            EventData::ImaSig {
+                digest,
+                name,
+                signature,
+            } => {
+                write_digest(false, digest, writer)?;
+                write_name(true, name, writer)?;
+                write_signature(signature, writer)?;
+            }
+            EventData::ImaBuf {
+                digest,
+                name,
+                buffer,
+            } => {
+                write_digest(false, digest, writer)?;
+                write_name(true, name, writer)?;
+                write_buffer(buffer, writer)?;
+            }
+            EventData::ImaModsig {
+                digest,
+                name,
+                signature,
+                modsig,
+            } => {
+                write_digest(false, digest, writer)?;
+                write_name(true, name, writer)?;
+                write_signature(signature, writer)?;
+                write_modsig(modsig, writer)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Render the event data as the space-separated `field:value` tokens used
+    /// by the ASCII measurement format (not including the template name).
+    fn ascii_fields(&self) -> Result<Vec<String>, Error> {
+        Ok(match self {
+            EventData::Ima { digest, name } => {
+                vec![hex::encode(&digest.digest), name.clone()]
+            }
+            EventData::ImaNg { digest, name } => {
+                vec![
+                    format!("{}:{}", algo_name(digest.algo)?, hex::encode(&digest.digest)),
+                    name.clone(),
+                ]
+            }
+            EventData::ImaSig {
+                digest,
+                name,
+                signature,
+            } => {
+                vec![
+                    format!("{}:{}", algo_name(digest.algo)?, hex::encode(&digest.digest)),
+                    name.clone(),
+                    optional_signature_hex(signature)?,
+                ]
+            }
+            EventData::ImaBuf {
+                digest,
+                name,
+                buffer,
+            } => {
+                vec![
+                    format!("{}:{}", algo_name(digest.algo)?, hex::encode(&digest.digest)),
+                    name.clone(),
+                    hex::encode(&buffer.data),
+                ]
+            }
+            EventData::ImaModsig {
+                digest,
+                name,
+                signature,
+                modsig,
+            } => {
+                vec![
+                    format!("{}:{}", algo_name(digest.algo)?, hex::encode(&digest.digest)),
+                    name.clone(),
+                    optional_signature_hex(signature)?,
+                    format!(
+                        "{}:{}",
+                        algo_name(modsig.digest.algo)?,
+                        hex::encode(&modsig.digest.digest)
+                    ),
+                    hex::encode(&modsig.signature),
+                ]
+            }
+        })
+    }
+
+    /// Reconstruct event data from the ASCII measurement format's fields.
+    fn from_ascii_fields(template_name: &str, fields: &[&str]) -> Result<Self, Error> {
+        let bad = || Error::AsciiFormat(format!("{}: wrong field count", template_name));
+        match template_name {
+            "ima" => {
+                let [digest, name] = fields else { return Err(bad()) };
+                Ok(EventData::Ima {
+                    digest: ascii_digest(true, *digest)?,
+                    name: (*name).to_owned(),
+                })
+            }
+            "ima-ng" => {
+                let [digest, name] = fields else { return Err(bad()) };
+                Ok(EventData::ImaNg {
+                    digest: ascii_digest(false, *digest)?,
+                    name: (*name).to_owned(),
+                })
+            }
+            "ima-sig" => {
+                let [digest, name, sig] = fields else { return Err(bad()) };
+                Ok(EventData::ImaSig {
+                    digest: ascii_digest(false, *digest)?,
+                    name: (*name).to_owned(),
+                    signature: ascii_signature(*sig)?,
+                })
+            }
+            "ima-buf" => {
+                let [digest, name, buf] = fields else { return Err(bad()) };
+                Ok(EventData::ImaBuf {
+                    digest: ascii_digest(false, *digest)?,
+                    name: (*name).to_owned(),
+                    buffer: Buffer {
+                        data: hex::decode(buf)?,
+                    },
+                })
+            }
+            "ima-modsig" => {
+                let [digest, name, sig, dmodsig, modsig] = fields else { return Err(bad()) };
+                Ok(EventData::ImaModsig {
+                    digest: ascii_digest(false, *digest)?,
+                    name: (*name).to_owned(),
+                    signature: ascii_signature(*sig)?,
+                    modsig: Modsig {
+                        digest: ascii_digest(false, *dmodsig)?,
+                        signature: hex::decode(modsig)?,
+                    },
+                })
+            }
+            _ => Err(Error::UnsupportedTemplate(template_name.to_owned())),
+        }
+    }
+}
+
+/// Parse an ASCII digest field: `<hex>` for the legacy `ima` template, or
+/// `<algo>:<hex>` otherwise.
+fn ascii_digest(is_legacy_ima_template: bool, field: &str) -> Result<Digest, Error> {
+    if is_legacy_ima_template {
+        Ok(Digest {
+            algo: DigestAlgorithm::Sha1,
+            digest: hex::decode(field)?,
+        })
+    } else {
+        let (algo, digest) = field
+            .split_once(':')
+            .ok_or_else(|| Error::AsciiFormat(format!("digest missing algo: {}", field)))?;
+        Ok(Digest {
+            algo: DigestAlgorithm::from_str(algo)?,
+            digest: hex::decode(digest)?,
+        })
+    }
+}
+
+/// Parse an ASCII `sig` field: the hex-encoded `signature_v2_hdr` blob, or an
+/// empty field for an unsigned entry.
+fn ascii_signature(field: &str) -> Result<Option<Signature>, Error> {
+    let bytes = hex::decode(field)?;
+    parse_signature(&mut PrefixedReader::new(&bytes))
+}
+
+/// Helper that prepends the u32 length prefix [`parse_signature`] expects, so
+/// the same parser can be reused for a bare signature blob coming from ASCII.
+struct PrefixedReader<'a> {
+    prefix: [u8; 4],
+    pos: usize,
+    rest: &'a [u8],
+}
+
+impl<'a> PrefixedReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        PrefixedReader {
+            prefix: (bytes.len() as u32).to_le_bytes(),
+            pos: 0,
+            rest: bytes,
+        }
+    }
+}
+
+impl Read for PrefixedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pos < 4 {
+            let n = (4 - self.pos).min(buf.len());
+            buf[..n].copy_from_slice(&self.prefix[self.pos..self.pos + n]);
+            self.pos += n;
+            Ok(n)
+        } else {
+            self.rest.read(buf)
+        }
+    }
+}
+
+/// Read one DER TLV with the expected `tag`, returning its contents and the
+/// bytes following it.
+fn der_tlv(data: &[u8], tag: u8) -> Result<(&[u8], &[u8]), Error> {
+    if data.len() < 2 || data[0] != tag {
+        return Err(Error::DataError);
+    }
+    let (len, header) = match data[1] {
+        b if b < 0x80 => (b as usize, 2),
+        0x81 => (*data.get(2).ok_or(Error::DataError)? as usize, 3),
+        0x82 => {
+            let hi = *data.get(2).ok_or(Error::DataError)? as usize;
+            let lo = *data.get(3).ok_or(Error::DataError)? as usize;
+            ((hi << 8) | lo, 4)
+        }
+        _ => return Err(Error::DataError),
+    };
+    let end = header.checked_add(len).ok_or(Error::DataError)?;
+    if data.len() < end {
+        return Err(Error::DataError);
+    }
+    Ok((&data[header..end], &data[end..]))
+}
+
+/// Extract the raw `subjectPublicKey` BIT STRING contents out of a DER
+/// SubjectPublicKeyInfo (`SEQUENCE { AlgorithmIdentifier, BIT STRING }`).
+fn subject_public_key(spki: &[u8]) -> Result<Vec<u8>, Error> {
+    let (seq, _) = der_tlv(spki, 0x30)?;
+    let (_algorithm, after_algorithm) = der_tlv(seq, 0x30)?;
+    let (bitstring, _) = der_tlv(after_algorithm, 0x03)?;
+    // The first BIT STRING byte is the number of unused bits; drop it.
+    Ok(bitstring.get(1..).ok_or(Error::DataError)?.to_vec())
+}
+
+/// Compute the IMA keyid of a public key: the last four bytes of the SHA-1 over
+/// the `subjectPublicKey` BIT STRING contents (as ima-evm-utils does), read as
+/// a big-endian `u32`.
+fn ima_keyid(key: &PKey<Public>) -> Result<u32, Error> {
+    let public_key = subject_public_key(&key.public_key_to_der()?)?;
+    let mut hasher = Hasher::new(MessageDigest::sha1())?;
+    hasher.update(&public_key)?;
+    let digest = hasher.finish()?;
+    let tail: [u8; 4] = digest[digest.len() - 4..].try_into().unwrap();
+    Ok(u32::from_be_bytes(tail))
+}
+
+/// A keyring of public keys indexed by their IMA keyid, used to appraise the
+/// signatures carried by `ima-sig`/`ima-modsig` entries.
+#[derive(Debug, Default)]
+pub struct Verifier {
+    keys: HashMap<u32, PKey<Public>>,
+}
+
+impl Verifier {
+    pub fn new() -> Self {
+        Verifier::default()
+    }
+
+    /// Add a public key to the keyring, returning the keyid it was indexed under.
+    pub fn add_key(&mut self, key: PKey<Public>) -> Result<u32, Error> {
+        let keyid = ima_keyid(&key)?;
+        self.keys.insert(keyid, key);
+        Ok(keyid)
+    }
+
+    /// Validate `signature` over `contents` against the matching key.
+    fn verify(&self, signature: &Signature, contents: &[u8]) -> Result<(), Error> {
+        let key = self
+            .keys
+            .get(&signature.keyid)
+            .ok_or(Error::UnknownKeyId(signature.keyid))?;
+        let mut verifier = PkeyVerifier::new(message_digest(signature.algo)?, key)?;
+        verifier.update(contents)?;
+        if verifier.verify(&signature.signature)? {
+            Ok(())
+        } else {
+            Err(Error::BadSignature)
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Event {
     pub pcr_index: u32,
     #[serde(with = "hex")]
@@ -201,6 +744,46 @@ pub struct Event {
     pub data: EventData,
 }
 
+impl Event {
+    /// Appraise this event's signature against `verifier`, given the current
+    /// contents of the measured file. See [`EventData::verify`].
+    pub fn verify(&self, verifier: &Verifier, contents: &[u8]) -> Result<(), Error> {
+        self.data.verify(verifier, contents)
+    }
+
+    /// Serialize this event in the `binary_runtime_measurements` layout.
+    pub fn write_binary<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_u32::<LittleEndian>(self.pcr_index)?;
+        writer.write_all(&self.template_sha1)?;
+        let name = self.data.template_name();
+        writer.write_u32::<LittleEndian>(name.len() as u32)?;
+        writer.write_all(name.as_bytes())?;
+
+        let mut data = Vec::new();
+        self.data.write_binary(&mut data)?;
+        writer.write_u32::<LittleEndian>(data.len() as u32)?;
+        writer.write_all(&data)?;
+        Ok(())
+    }
+
+    /// Serialize this event as a single `ascii_runtime_measurements` line,
+    /// including the trailing newline.
+    pub fn write_ascii<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        write!(
+            writer,
+            "{} {} {}",
+            self.pcr_index,
+            hex::encode(self.template_sha1),
+            self.data.template_name(),
+        )?;
+        for field in self.data.ascii_fields()? {
+            write!(writer, " {}", field)?;
+        }
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct Parser<R: Read> {
     reader: R,
@@ -211,17 +794,48 @@ fn zeroed_vec(len: usize) -> Vec<u8> {
     vec![0; len]
 }
 
+/// Build the PCR extender tracking all banks the parsers replay into.
+fn new_pcr_tracker() -> PcrExtender {
+    PcrExtenderBuilder::new()
+        .add_digest_method(DigestAlgorithm::Sha1)
+        .add_digest_method(DigestAlgorithm::Sha256)
+        .add_digest_method(DigestAlgorithm::Sha384)
+        .add_digest_method(DigestAlgorithm::Sha512)
+        .build()
+}
+
 impl<R: Read> Parser<R> {
     pub fn new(reader: R) -> Self {
         // Return a new Parser instance
         Parser {
             reader,
-            pcr_tracker: PcrExtenderBuilder::new()
-                .add_digest_method(DigestAlgorithm::Sha1)
-                .add_digest_method(DigestAlgorithm::Sha256)
-                .add_digest_method(DigestAlgorithm::Sha384)
-                .add_digest_method(DigestAlgorithm::Sha512)
-                .build(),
+            pcr_tracker: new_pcr_tracker(),
+        }
+    }
+
+    pub fn pcr_values(self) -> PcrValues {
+        pcr_extender_to_values(self.pcr_tracker)
+    }
+}
+
+/// Parser for the textual `ascii_runtime_measurements` representation.
+///
+/// Produces exactly the same [`Event`]s and replays the same PCR banks as the
+/// binary [`Parser`], so either representation can be normalized into the
+/// other without loss.
+#[derive(Debug)]
+pub struct AsciiParser<R: BufRead> {
+    reader: R,
+    line: String,
+    pcr_tracker: PcrExtender,
+}
+
+impl<R: BufRead> AsciiParser<R> {
+    pub fn new(reader: R) -> Self {
+        AsciiParser {
+            reader,
+            line: String::new(),
+            pcr_tracker: new_pcr_tracker(),
         }
     }
 
@@ -230,6 +844,67 @@ impl<R: Read> Parser<R> {
     }
 }
 
+impl<R: BufRead> FallibleIterator for AsciiParser<R> {
+    type Item = Event;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Event>, Error> {
+        // Skip blank lines; only a genuine EOF (read of 0 bytes) ends the stream.
+        let line = loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            let line = self.line.trim_end_matches('\n');
+            if !line.is_empty() {
+                break line;
+            }
+        };
+
+        // `pcr template-hash template-name field [field ...]`
+        let mut parts = line.splitn(4, ' ');
+        let pcr_index = parts
+            .next()
+            .ok_or_else(|| Error::AsciiFormat(line.to_owned()))?
+            .parse::<u32>()?;
+        let template_sha1_hex = parts
+            .next()
+            .ok_or_else(|| Error::AsciiFormat(line.to_owned()))?;
+        let template_name = parts
+            .next()
+            .ok_or_else(|| Error::AsciiFormat(line.to_owned()))?;
+        let rest = parts
+            .next()
+            .ok_or_else(|| Error::AsciiFormat(line.to_owned()))?;
+
+        let template_sha1: [u8; 20] = hex::decode(template_sha1_hex)?
+            .try_into()
+            .map_err(|_| Error::AsciiFormat(line.to_owned()))?;
+
+        // Split off exactly as many fields as the template carries. For
+        // `ima`/`ima-ng` the name is the final field, so any spaces it contains
+        // are preserved.
+        let field_count = match template_name {
+            "ima-sig" | "ima-buf" => 3,
+            "ima-modsig" => 5,
+            _ => 2,
+        };
+        let fields: Vec<&str> = rest.splitn(field_count, ' ').collect();
+        let data = EventData::from_ascii_fields(template_name, &fields)?;
+
+        // Replay the PCR banks over the reconstructed binary template data.
+        let mut template_data = Vec::new();
+        data.write_binary(&mut template_data)?;
+        self.pcr_tracker.extend(pcr_index, &template_data)?;
+
+        Ok(Some(Event {
+            pcr_index,
+            template_sha1,
+            data,
+        }))
+    }
+}
+
 pub type PcrValues = BTreeMap<u32, PcrValue>;
 
 fn pcr_extender_to_values(ext: PcrExtender) -> PcrValues {
@@ -273,7 +948,7 @@ fn pcr_extender_to_values(ext: PcrExtender) -> PcrValues {
     vals
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PcrValue {
     #[serde(with = "hex")]
     pub sha1: [u8; 20],
@@ -296,6 +971,121 @@ impl Default for PcrValue {
     }
 }
 
+impl PcrValue {
+    /// The digest for a single bank, as a byte slice.
+    fn bank(&self, algo: DigestAlgorithm) -> Option<&[u8]> {
+        Some(match algo {
+            DigestAlgorithm::Sha1 => &self.sha1,
+            DigestAlgorithm::Sha256 => &self.sha256,
+            DigestAlgorithm::Sha384 => &self.sha384,
+            DigestAlgorithm::Sha512 => &self.sha512,
+            _ => return None,
+        })
+    }
+}
+
+/// The banks compared when verifying a full set of [`PcrValues`].
+const VERIFIED_BANKS: [DigestAlgorithm; 4] = [
+    DigestAlgorithm::Sha1,
+    DigestAlgorithm::Sha256,
+    DigestAlgorithm::Sha384,
+    DigestAlgorithm::Sha512,
+];
+
+/// A single per-PCR, per-bank discrepancy found during verification.
+#[derive(Debug, Serialize)]
+pub struct PcrMismatch {
+    pub pcr: u32,
+    pub algo: DigestAlgorithm,
+    #[serde(with = "hex")]
+    pub expected: Vec<u8>,
+    #[serde(with = "hex")]
+    pub found: Vec<u8>,
+}
+
+/// Verification surface for replayed PCR banks.
+///
+/// Implemented for [`PcrValues`] so a log's replayed state can be proven
+/// consistent with an expected set of values, or with digests quoted from
+/// hardware PCR state.
+pub trait PcrVerify {
+    /// Compare against `expected`, returning [`Error::PcrMismatch`] with the
+    /// full structured diff on any difference. Only the PCRs and banks that
+    /// `expected` actually populates are checked, so a caller holding a single
+    /// bank of quoted values does not need to supply a full four-bank replay.
+    fn verify_against(&self, expected: &PcrValues) -> Result<(), Error>;
+
+    /// Confirm that the replayed log reproduces a set of externally supplied
+    /// (e.g. TPM-quoted) PCR digests for a single `bank`.
+    fn verify_quote(
+        &self,
+        bank: DigestAlgorithm,
+        quoted: &BTreeMap<u32, Vec<u8>>,
+    ) -> Result<(), Error>;
+}
+
+impl PcrVerify for PcrValues {
+    fn verify_against(&self, expected: &PcrValues) -> Result<(), Error> {
+        let mut mismatches = Vec::new();
+        let default = PcrValue::default();
+
+        // Only compare banks that `expected` actually carries: the replayed log
+        // always fills every bank, so an attestation input covering a single
+        // bank must not flag the others as mismatched.
+        for (pcr, want) in expected {
+            let found = self.get(pcr).unwrap_or(&default);
+            for algo in VERIFIED_BANKS {
+                let e = want.bank(algo).unwrap_or(&[]);
+                if e.iter().all(|b| *b == 0x00) {
+                    continue;
+                }
+                let f = found.bank(algo).unwrap_or(&[]);
+                if f != e {
+                    mismatches.push(PcrMismatch {
+                        pcr: *pcr,
+                        algo,
+                        expected: e.to_vec(),
+                        found: f.to_vec(),
+                    });
+                }
+            }
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::PcrMismatch(mismatches))
+        }
+    }
+
+    fn verify_quote(
+        &self,
+        bank: DigestAlgorithm,
+        quoted: &BTreeMap<u32, Vec<u8>>,
+    ) -> Result<(), Error> {
+        let default = PcrValue::default();
+        let mut mismatches = Vec::new();
+
+        for (pcr, expected) in quoted {
+            let found = self.get(pcr).unwrap_or(&default).bank(bank).unwrap_or(&[]);
+            if found != expected.as_slice() {
+                mismatches.push(PcrMismatch {
+                    pcr: *pcr,
+                    algo: bank,
+                    expected: expected.clone(),
+                    found: found.to_vec(),
+                });
+            }
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::PcrMismatch(mismatches))
+        }
+    }
+}
+
 impl<R: Read> FallibleIterator for Parser<R> {
     type Item = Event;
     type Error = Error;
@@ -339,15 +1129,819 @@ impl<R: Read> FallibleIterator for Parser<R> {
     }
 }
 
+impl Parser<std::io::Cursor<Vec<u8>>> {
+    /// Construct a borrowing, zero-copy parser over an in-memory log.
+    ///
+    /// Unlike [`Parser::new`], which reads from a streaming source and
+    /// allocates for every field, [`SliceParser`] borrows digests, names and
+    /// signature payloads directly out of `data`, so iterating a mapped log
+    /// file produces near-zero heap traffic.
+    pub fn from_slice(data: &[u8]) -> SliceParser<'_> {
+        SliceParser::new(data)
+    }
+}
+
+/// A borrowed digest pointing into the backing log buffer.
+#[derive(Debug, Serialize)]
+pub struct DigestRef<'a> {
+    algo: DigestAlgorithm,
+    #[serde(with = "hex")]
+    digest: &'a [u8],
+}
+
+/// A borrowed `signature_v2_hdr`; the raw signature bytes alias the buffer.
+#[derive(Debug, Serialize)]
+pub struct SignatureRef<'a> {
+    pub algo: DigestAlgorithm,
+    pub keyid: u32,
+    #[serde(with = "hex")]
+    pub signature: &'a [u8],
+}
+
+/// The borrowed counterpart of [`EventData`]; see [`SliceParser`].
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum EventDataRef<'a> {
+    Ima {
+        digest: DigestRef<'a>,
+        name: &'a str,
+    },
+    ImaNg {
+        digest: DigestRef<'a>,
+        name: &'a str,
+    },
+    ImaSig {
+        digest: DigestRef<'a>,
+        name: &'a str,
+        signature: Option<SignatureRef<'a>>,
+    },
+    ImaBuf {
+        digest: DigestRef<'a>,
+        name: &'a str,
+        #[serde(with = "hex")]
+        buffer: &'a [u8],
+    },
+    ImaModsig {
+        digest: DigestRef<'a>,
+        name: &'a str,
+        signature: Option<SignatureRef<'a>>,
+        modsig: ModsigRef<'a>,
+    },
+}
+
+/// A borrowed `modsig` field; the `d-modsig` digest and raw signature alias the buffer.
+#[derive(Debug, Serialize)]
+pub struct ModsigRef<'a> {
+    digest: DigestRef<'a>,
+    #[serde(with = "hex")]
+    signature: &'a [u8],
+}
+
+/// The borrowed counterpart of [`Event`]; see [`SliceParser`].
+#[derive(Debug, Serialize)]
+pub struct EventRef<'a> {
+    pub pcr_index: u32,
+    #[serde(with = "hex")]
+    pub template_sha1: &'a [u8],
+    #[serde(flatten)]
+    pub data: EventDataRef<'a>,
+}
+
+/// A cursor over a byte slice that hands out borrowed sub-slices.
+struct SliceReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> SliceReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        SliceReader { data, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.remaining() < len {
+            return Err(Error::DataError);
+        }
+        let out = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16_be(&mut self) -> Result<u16, Error> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32_le(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u32_be(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
+
+fn slice_digest<'a>(
+    is_legacy_ima_template: bool,
+    reader: &mut SliceReader<'a>,
+) -> Result<DigestRef<'a>, Error> {
+    if is_legacy_ima_template {
+        Ok(DigestRef {
+            algo: DigestAlgorithm::Sha1,
+            digest: reader.take(20)?,
+        })
+    } else {
+        let len = reader.u32_le()? as usize;
+        let buf = reader.take(len)?;
+        let split = match buf.iter().position(|&r| r == b':') {
+            Some(p) => p,
+            None => return Err(Error::DataError),
+        };
+        let (algo, digest) = buf.split_at(split + 2);
+        let algo = std::str::from_utf8(algo)?.trim_end_matches(":\0");
+        Ok(DigestRef {
+            algo: DigestAlgorithm::from_str(algo)?,
+            digest,
+        })
+    }
+}
+
+fn slice_name<'a>(
+    is_legacy_ima_template: bool,
+    reader: &mut SliceReader<'a>,
+) -> Result<&'a str, Error> {
+    let len = reader.u32_le()? as usize;
+    let buf = reader.take(len)?;
+    let s = if is_legacy_ima_template {
+        std::str::from_utf8(buf)?
+    } else {
+        CStr::from_bytes_with_nul(buf)?.to_str()?
+    };
+    Ok(s.trim_end_matches('\0'))
+}
+
+fn slice_signature<'a>(reader: &mut SliceReader<'a>) -> Result<Option<SignatureRef<'a>>, Error> {
+    let len = reader.u32_le()?;
+    if len == 0 {
+        return Ok(None);
+    }
+    if reader.u8()? != EVM_IMA_XATTR_DIGSIG {
+        return Err(Error::DataError);
+    }
+    let version = reader.u8()?;
+    if version != 2 {
+        return Err(Error::UnsupportedSignatureVersion(version));
+    }
+    let algo = digest_algo_from_hash_info(reader.u8()?)?;
+    let keyid = reader.u32_be()?;
+    let sig_size = reader.u16_be()? as usize;
+    Ok(Some(SignatureRef {
+        algo,
+        keyid,
+        signature: reader.take(sig_size)?,
+    }))
+}
+
+fn slice_buffer<'a>(reader: &mut SliceReader<'a>) -> Result<&'a [u8], Error> {
+    let len = reader.u32_le()? as usize;
+    reader.take(len)
+}
+
+fn slice_modsig<'a>(reader: &mut SliceReader<'a>) -> Result<ModsigRef<'a>, Error> {
+    let digest = slice_digest(false, reader)?;
+    let len = reader.u32_le()? as usize;
+    Ok(ModsigRef {
+        digest,
+        signature: reader.take(len)?,
+    })
+}
+
+impl<'a> EventDataRef<'a> {
+    fn parse(template_name: &str, data: &'a [u8]) -> Result<Self, Error> {
+        let reader = &mut SliceReader::new(data);
+        match template_name {
+            "ima" => Ok(EventDataRef::Ima {
+                digest: slice_digest(true, reader)?,
+                name: slice_name(true, reader)?,
+            }),
+            "ima-ng" => Ok(EventDataRef::ImaNg {
+                digest: slice_digest(false, reader)?,
+                name: slice_name(true, reader)?,
+            }),
+            "ima-sig" => Ok(EventDataRef::ImaSig {
+                digest: slice_digest(false, reader)?,
+                name: slice_name(false, reader)?,
+                signature: slice_signature(reader)?,
+            }),
+            "ima-buf" => Ok(EventDataRef::ImaBuf {
+                digest: slice_digest(false, reader)?,
+                name: slice_name(false, reader)?,
+                buffer: slice_buffer(reader)?,
+            }),
+            "ima-modsig" => Ok(EventDataRef::ImaModsig {
+                digest: slice_digest(false, reader)?,
+                name: slice_name(false, reader)?,
+                signature: slice_signature(reader)?,
+                modsig: slice_modsig(reader)?,
+            }),
+            _ => Err(Error::UnsupportedTemplate(template_name.to_owned())),
+        }
+    }
+}
+
+/// Borrowing parser over an in-memory measurement log. See [`Parser::from_slice`].
+#[derive(Debug)]
+pub struct SliceParser<'a> {
+    reader: SliceReader<'a>,
+    pcr_tracker: PcrExtender,
+}
+
+impl<'a> SliceParser<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        SliceParser {
+            reader: SliceReader::new(data),
+            pcr_tracker: new_pcr_tracker(),
+        }
+    }
+
+    pub fn pcr_values(self) -> PcrValues {
+        pcr_extender_to_values(self.pcr_tracker)
+    }
+}
+
+impl<'a> FallibleIterator for SliceParser<'a> {
+    type Item = EventRef<'a>;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<EventRef<'a>>, Error> {
+        if self.reader.remaining() == 0 {
+            return Ok(None);
+        }
+
+        let pcr_index = self.reader.u32_le()?;
+        let template_sha1 = self.reader.take(20)?;
+        let template_name_size = self.reader.u32_le()? as usize;
+        let template_name = std::str::from_utf8(self.reader.take(template_name_size)?)?;
+
+        let eventdata_len = self.reader.u32_le()? as usize;
+        let event_data = self.reader.take(eventdata_len)?;
+        let data = EventDataRef::parse(template_name, event_data)?;
+
+        self.pcr_tracker.extend(pcr_index, event_data)?;
+
+        Ok(Some(EventRef {
+            pcr_index,
+            template_sha1,
+            data,
+        }))
+    }
+}
+
+/// Async, "follow"-capable parser for measurement logs that grow at runtime.
+///
+/// Available with the `async` feature. Unlike the blocking [`Parser`], which
+/// stops at the first short read, [`AsyncParser`] buffers bytes until a whole
+/// event is present, so a half-written entry appended by the kernel is never
+/// parsed. When the underlying source is exhausted mid-stream, [`next`] yields
+/// `Ok(None)` — "no event yet" — rather than terminating; call it again once
+/// more bytes have been appended to resume, analogous to `tail -f`.
+///
+/// [`next`]: AsyncParser::next
+#[cfg(feature = "async")]
+pub struct AsyncParser<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    pcr_tracker: PcrExtender,
+}
+
+#[cfg(feature = "async")]
+impl<R: tokio::io::AsyncRead + Unpin> AsyncParser<R> {
+    pub fn new(reader: R) -> Self {
+        AsyncParser {
+            reader,
+            buffer: Vec::new(),
+            pcr_tracker: new_pcr_tracker(),
+        }
+    }
+
+    pub fn pcr_values(self) -> PcrValues {
+        pcr_extender_to_values(self.pcr_tracker)
+    }
+
+    /// Read the next event, or `Ok(None)` if the source is currently exhausted
+    /// and no complete event is buffered yet.
+    pub async fn next(&mut self) -> Result<Option<Event>, Error> {
+        use tokio::io::AsyncReadExt;
+
+        loop {
+            if let Some(event) = self.try_parse_buffered()? {
+                return Ok(Some(event));
+            }
+
+            let mut chunk = [0u8; 4096];
+            let read = self.reader.read(&mut chunk).await?;
+            if read == 0 {
+                // EOF mid-stream: the event being written is not complete yet.
+                return Ok(None);
+            }
+            self.buffer.extend_from_slice(&chunk[..read]);
+        }
+    }
+
+    /// Try to parse one whole event out of the front of the buffer. Returns
+    /// `Ok(None)` when the buffer does not yet hold a complete event, leaving
+    /// the partial bytes untouched for the next read.
+    fn try_parse_buffered(&mut self) -> Result<Option<Event>, Error> {
+        let buf = &self.buffer;
+        // header: pcr_index(4) + template_sha1(20) + template_name_size(4)
+        if buf.len() < 28 {
+            return Ok(None);
+        }
+        let name_size = u32::from_le_bytes(buf[24..28].try_into().unwrap()) as usize;
+        let data_len_off = 28 + name_size;
+        if buf.len() < data_len_off + 4 {
+            return Ok(None);
+        }
+        let eventdata_len =
+            u32::from_le_bytes(buf[data_len_off..data_len_off + 4].try_into().unwrap()) as usize;
+        let total = data_len_off + 4 + eventdata_len;
+        if buf.len() < total {
+            return Ok(None);
+        }
+
+        let pcr_index = u32::from_le_bytes(buf[0..4].try_into().unwrap());
+        let template_sha1: [u8; 20] = buf[4..24].try_into().unwrap();
+        let template_name = std::str::from_utf8(&buf[28..28 + name_size])?.to_owned();
+        let event_data = &buf[data_len_off + 4..total];
+        let data = EventData::parse(&template_name, &mut &event_data[..])?;
+
+        self.pcr_tracker.extend(pcr_index, event_data)?;
+
+        let event = Event {
+            pcr_index,
+            template_sha1,
+            data,
+        };
+        self.buffer.drain(..total);
+        Ok(Some(event))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::TryFrom;
     use std::fs::File;
     use std::path::Path;
 
+    use std::collections::BTreeMap;
+    use std::io::Cursor;
+
     use fallible_iterator::FallibleIterator;
+    use openssl::hash::MessageDigest;
+    use tpmless_tpm2::DigestAlgorithm;
+
+    use crate::{
+        AsciiParser, Buffer, Digest, Error, Event, EventData, Modsig, Parser, PcrValue, PcrValues,
+        PcrVerify, Signature, Verifier,
+    };
+
+    /// Build a small two-entry `ima-ng` log as in-memory binary bytes, using
+    /// the crate's own binary writer.
+    fn sample_binary_log() -> Vec<u8> {
+        let events = [
+            Event {
+                pcr_index: 10,
+                template_sha1: [0x11; 20],
+                data: EventData::ImaNg {
+                    digest: Digest {
+                        algo: DigestAlgorithm::Sha256,
+                        digest: vec![0xAB; 32],
+                    },
+                    name: "/usr/bin/bash".to_owned(),
+                },
+            },
+            Event {
+                pcr_index: 10,
+                template_sha1: [0x22; 20],
+                data: EventData::ImaNg {
+                    digest: Digest {
+                        algo: DigestAlgorithm::Sha256,
+                        digest: vec![0xCD; 32],
+                    },
+                    name: "/usr/lib/libc.so".to_owned(),
+                },
+            },
+        ];
+
+        let mut bytes = Vec::new();
+        for event in &events {
+            event.write_binary(&mut bytes).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_binary_ascii_round_trip() {
+        let binary = sample_binary_log();
+
+        // binary -> Event -> ASCII
+        let mut parser = Parser::new(Cursor::new(binary.clone()));
+        let mut ascii = Vec::new();
+        while let Some(event) = parser.next().expect("binary parse") {
+            event.write_ascii(&mut ascii).expect("ascii write");
+        }
+        let binary_pcrs = parser.pcr_values();
+
+        // ASCII -> Event -> binary, which must reproduce the original bytes.
+        let mut ascii_parser = AsciiParser::new(Cursor::new(ascii));
+        let mut roundtripped = Vec::new();
+        while let Some(event) = ascii_parser.next().expect("ascii parse") {
+            event.write_binary(&mut roundtripped).expect("binary write");
+        }
+        let ascii_pcrs = ascii_parser.pcr_values();
+
+        assert_eq!(binary, roundtripped, "binary<->ASCII round trip diverged");
+        assert_eq!(
+            binary_pcrs, ascii_pcrs,
+            "replayed PCRs diverged between binary and ASCII parsers"
+        );
+    }
+
+    fn pcr_values_with_sha256(pcr: u32, sha256: [u8; 32]) -> PcrValues {
+        let mut values = PcrValues::new();
+        values.insert(
+            pcr,
+            PcrValue {
+                sha256,
+                ..Default::default()
+            },
+        );
+        values
+    }
+
+    #[test]
+    fn test_verify_against_match_and_mismatch() {
+        let replayed = pcr_values_with_sha256(10, [0x42; 32]);
+
+        // Identical expectation passes.
+        let expected = pcr_values_with_sha256(10, [0x42; 32]);
+        replayed.verify_against(&expected).expect("should match");
+
+        // A differing digest surfaces a structured diff.
+        let expected = pcr_values_with_sha256(10, [0x43; 32]);
+        match replayed.verify_against(&expected) {
+            Err(Error::PcrMismatch(mismatches)) => {
+                assert_eq!(mismatches.len(), 1);
+                assert_eq!(mismatches[0].pcr, 10);
+                assert_eq!(mismatches[0].found, vec![0x42; 32]);
+                assert_eq!(mismatches[0].expected, vec![0x43; 32]);
+            }
+            other => panic!("expected PcrMismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_verify_quote_match_and_mismatch() {
+        let replayed = pcr_values_with_sha256(10, [0x42; 32]);
+
+        let mut quoted = BTreeMap::new();
+        quoted.insert(10u32, vec![0x42; 32]);
+        replayed
+            .verify_quote(DigestAlgorithm::Sha256, &quoted)
+            .expect("quote should match");
+
+        quoted.insert(10u32, vec![0x99; 32]);
+        match replayed.verify_quote(DigestAlgorithm::Sha256, &quoted) {
+            Err(Error::PcrMismatch(mismatches)) => {
+                assert_eq!(mismatches.len(), 1);
+                assert_eq!(mismatches[0].pcr, 10);
+            }
+            other => panic!("expected PcrMismatch, got {:?}", other),
+        }
+    }
+
+    /// SHA-1 over the binary event-data blob, i.e. what the kernel stores as
+    /// the per-entry `template_sha1`. Used to build self-consistent fixtures.
+    fn template_hash(data: &EventData) -> [u8; 20] {
+        let mut blob = Vec::new();
+        data.write_binary(&mut blob).unwrap();
+        let digest = openssl::hash::hash(openssl::hash::MessageDigest::sha1(), &blob).unwrap();
+        digest.as_ref().try_into().unwrap()
+    }
+
+    fn event(pcr: u32, data: EventData) -> Event {
+        Event {
+            pcr_index: pcr,
+            template_sha1: template_hash(&data),
+            data,
+        }
+    }
 
-    use crate::{EventData, Parser};
+    fn sha256(contents: &[u8]) -> Vec<u8> {
+        openssl::hash::hash(openssl::hash::MessageDigest::sha256(), contents)
+            .unwrap()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_unsigned_sig_entry_parses() {
+        // An `ima-sig` entry whose file was not signed carries a zero-length sig
+        // field; it must parse as `None` and survive a binary round trip rather
+        // than aborting the iterator.
+        let events = vec![event(
+            10,
+            EventData::ImaSig {
+                digest: Digest {
+                    algo: DigestAlgorithm::Sha256,
+                    digest: vec![0x04; 32],
+                },
+                name: "/usr/bin/unsigned".to_owned(),
+                signature: None,
+            },
+        )];
+        let mut binary = Vec::new();
+        events[0].write_binary(&mut binary).unwrap();
+
+        let mut parser = Parser::new(Cursor::new(binary.clone()));
+        let event = parser.next().unwrap().expect("one entry");
+        match &event.data {
+            EventData::ImaSig { signature, .. } => assert!(signature.is_none()),
+            other => panic!("expected ImaSig, got {:?}", other),
+        }
+        let mut reencoded = Vec::new();
+        event.write_binary(&mut reencoded).unwrap();
+        assert_eq!(binary, reencoded);
+    }
+
+    #[test]
+    fn test_sign_parse_verify_round_trip() {
+        use openssl::pkey::PKey;
+        use openssl::rsa::Rsa;
+        use openssl::sign::Signer;
+
+        let contents = b"the measured file contents";
+
+        let rsa = Rsa::generate(2048).unwrap();
+        let private = PKey::from_rsa(rsa).unwrap();
+        let public = PKey::public_key_from_der(&private.public_key_to_der().unwrap()).unwrap();
+
+        let mut signer = Signer::new(MessageDigest::sha256(), &private).unwrap();
+        signer.update(contents).unwrap();
+        let sig_bytes = signer.sign_to_vec().unwrap();
+
+        let mut verifier = Verifier::new();
+        let keyid = verifier.add_key(public).unwrap();
+
+        let signed = event(
+            10,
+            EventData::ImaSig {
+                digest: Digest {
+                    algo: DigestAlgorithm::Sha256,
+                    digest: sha256(contents),
+                },
+                name: "/usr/bin/signed".to_owned(),
+                signature: Some(Signature {
+                    algo: DigestAlgorithm::Sha256,
+                    keyid,
+                    signature: sig_bytes,
+                }),
+            },
+        );
+
+        // Write the signed entry out and parse it back, so the signature being
+        // verified is the one decoded from the binary log.
+        let mut binary = Vec::new();
+        signed.write_binary(&mut binary).unwrap();
+        let signed = Parser::new(Cursor::new(binary))
+            .next()
+            .unwrap()
+            .expect("one entry");
+
+        // A matching key and unchanged contents verify.
+        signed.verify(&verifier, contents).expect("should verify");
+
+        // Tampered contents fail the digest check before the signature is even
+        // consulted.
+        match signed.verify(&verifier, b"tampered") {
+            Err(Error::DigestMismatch) => {}
+            other => panic!("expected DigestMismatch, got {:?}", other),
+        }
+
+        // An unsigned entry cannot be appraised.
+        let unsigned = event(
+            10,
+            EventData::ImaSig {
+                digest: Digest {
+                    algo: DigestAlgorithm::Sha256,
+                    digest: sha256(contents),
+                },
+                name: "/usr/bin/signed".to_owned(),
+                signature: None,
+            },
+        );
+        match unsigned.verify(&verifier, contents) {
+            Err(Error::NotSigned) => {}
+            other => panic!("expected NotSigned, got {:?}", other),
+        }
+    }
+
+    /// Build a log exercising every template, each entry carrying the real
+    /// SHA-1 of its own event data.
+    fn all_templates_log(signature: Option<Signature>) -> Vec<Event> {
+        let sig_digest = Digest {
+            algo: DigestAlgorithm::Sha1,
+            digest: vec![0x01; 20],
+        };
+        vec![
+            event(
+                10,
+                EventData::Ima {
+                    digest: Digest {
+                        algo: DigestAlgorithm::Sha1,
+                        digest: vec![0x02; 20],
+                    },
+                    name: "/init".to_owned(),
+                },
+            ),
+            event(
+                10,
+                EventData::ImaNg {
+                    digest: Digest {
+                        algo: DigestAlgorithm::Sha256,
+                        digest: vec![0x03; 32],
+                    },
+                    name: "/usr/bin/bash".to_owned(),
+                },
+            ),
+            event(
+                10,
+                EventData::ImaSig {
+                    digest: Digest {
+                        algo: DigestAlgorithm::Sha256,
+                        digest: vec![0x04; 32],
+                    },
+                    name: "/usr/bin/sudo".to_owned(),
+                    signature: signature.clone(),
+                },
+            ),
+            event(
+                11,
+                EventData::ImaBuf {
+                    digest: Digest {
+                        algo: DigestAlgorithm::Sha256,
+                        digest: vec![0x05; 32],
+                    },
+                    name: "boot_aggregate".to_owned(),
+                    buffer: Buffer {
+                        data: vec![0xDE, 0xAD, 0xBE, 0xEF],
+                    },
+                },
+            ),
+            event(
+                11,
+                EventData::ImaModsig {
+                    digest: Digest {
+                        algo: DigestAlgorithm::Sha256,
+                        digest: vec![0x06; 32],
+                    },
+                    name: "/usr/lib/modules/mod.ko".to_owned(),
+                    signature,
+                    modsig: Modsig {
+                        digest: sig_digest,
+                        signature: vec![0x07; 16],
+                    },
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_all_templates_round_trip() {
+        for signature in [
+            None,
+            Some(Signature {
+                algo: DigestAlgorithm::Sha256,
+                keyid: 0xDEADBEEF,
+                signature: vec![0xAB; 64],
+            }),
+        ] {
+            let events = all_templates_log(signature);
+
+            let mut binary = Vec::new();
+            for event in &events {
+                event.write_binary(&mut binary).unwrap();
+            }
+
+            // binary -> Event -> binary must be byte-identical, and each parsed
+            // entry's template_sha1 must still match the SHA-1 of its re-emitted
+            // event data.
+            let mut parser = Parser::new(Cursor::new(binary.clone()));
+            let mut reencoded = Vec::new();
+            let mut count = 0;
+            while let Some(event) = parser.next().unwrap() {
+                assert_eq!(
+                    event.template_sha1,
+                    template_hash(&event.data),
+                    "template hash diverged for {:?}",
+                    event.data
+                );
+                event.write_binary(&mut reencoded).unwrap();
+                count += 1;
+            }
+            assert_eq!(count, 5);
+            assert_eq!(binary, reencoded, "binary round trip diverged");
+
+            // binary -> Event -> ASCII -> Event -> binary must also be stable.
+            let mut ascii = Vec::new();
+            let mut parser = Parser::new(Cursor::new(binary.clone()));
+            while let Some(event) = parser.next().unwrap() {
+                event.write_ascii(&mut ascii).unwrap();
+            }
+            let mut ascii_parser = AsciiParser::new(Cursor::new(ascii));
+            let mut from_ascii = Vec::new();
+            while let Some(event) = ascii_parser.next().unwrap() {
+                event.write_binary(&mut from_ascii).unwrap();
+            }
+            assert_eq!(binary, from_ascii, "ASCII round trip diverged");
+        }
+    }
+
+    #[test]
+    fn test_slice_parser_matches_parser() {
+        let events = all_templates_log(Some(Signature {
+            algo: DigestAlgorithm::Sha256,
+            keyid: 0x01020304,
+            signature: vec![0xCD; 48],
+        }));
+        let mut binary = Vec::new();
+        for event in &events {
+            event.write_binary(&mut binary).unwrap();
+        }
+
+        // Pull the decoded (name, algo, digest) triple out of either enum so the
+        // two parsers can be compared field-for-field.
+        fn owned_fields(d: &EventData) -> (&str, DigestAlgorithm, &[u8]) {
+            match d {
+                EventData::Ima { digest, name }
+                | EventData::ImaNg { digest, name }
+                | EventData::ImaSig { digest, name, .. }
+                | EventData::ImaBuf { digest, name, .. }
+                | EventData::ImaModsig { digest, name, .. } => {
+                    (name.as_str(), digest.algo, &digest.digest)
+                }
+            }
+        }
+        fn borrowed_fields<'a>(d: &crate::EventDataRef<'a>) -> (&'a str, DigestAlgorithm, &'a [u8]) {
+            match d {
+                crate::EventDataRef::Ima { digest, name }
+                | crate::EventDataRef::ImaNg { digest, name }
+                | crate::EventDataRef::ImaSig { digest, name, .. }
+                | crate::EventDataRef::ImaBuf { digest, name, .. }
+                | crate::EventDataRef::ImaModsig { digest, name, .. } => {
+                    (name, digest.algo, digest.digest)
+                }
+            }
+        }
+
+        // The borrowing SliceParser must agree with the owning Parser on every
+        // template's header and decoded fields, and replay the same PCRs.
+        let mut owning = Parser::new(Cursor::new(binary.clone()));
+        let mut slicing = crate::SliceParser::new(&binary);
+        loop {
+            let owned = owning.next().unwrap();
+            let borrowed = slicing.next().unwrap();
+            match (owned, borrowed) {
+                (Some(owned), Some(borrowed)) => {
+                    let (o_name, o_algo, o_digest) = owned_fields(&owned.data);
+                    let (b_name, b_algo, b_digest) = borrowed_fields(&borrowed.data);
+                    assert_eq!(owned.pcr_index, borrowed.pcr_index);
+                    assert_eq!(&owned.template_sha1[..], borrowed.template_sha1);
+                    assert_eq!(o_name, b_name);
+                    assert_eq!(format!("{:?}", o_algo), format!("{:?}", b_algo));
+                    assert_eq!(o_digest, b_digest);
+                }
+                (None, None) => break,
+                _ => panic!("parsers disagreed on event count"),
+            }
+        }
+
+        let mut owning = Parser::new(Cursor::new(binary.clone()));
+        while owning.next().unwrap().is_some() {}
+        let owning_pcrs = owning.pcr_values();
+
+        let mut slicing = crate::SliceParser::new(&binary);
+        while slicing.next().unwrap().is_some() {}
+        let slicing_pcrs = slicing.pcr_values();
+
+        assert_eq!(owning_pcrs, slicing_pcrs);
+    }
 
     #[test]
     fn test_ima_ng() {